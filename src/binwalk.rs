@@ -0,0 +1,289 @@
+//! Core scanning and extraction engine.
+
+use std::collections::HashMap;
+
+use crate::common::load_name_dictionary;
+use crate::extractors::{default_extractors, CarveExtractor, Extractor, ExtractionResult};
+use crate::signatures::{
+    default_signatures, union_of, DifferenceMatcher, MatchAll, MatchNone, Signature,
+    SignatureMatcher, SignatureResult,
+};
+
+/// Results of a signature scan: one entry per match found in the target data.
+pub type AnalysisResults = Vec<SignatureResult>;
+
+/// Error type returned by fallible [`Binwalk`] operations.
+#[derive(Debug, Clone)]
+pub struct BinwalkError {
+    pub message: String,
+}
+
+impl std::fmt::Display for BinwalkError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for BinwalkError {}
+
+/// The stage of a scan or extraction run that triggered a progress callback.
+#[derive(Debug, Clone)]
+pub enum ProgressEvent {
+    /// A new signature match was found.
+    SignatureMatch(SignatureResult),
+    /// An extractor is about to run for the given signature match.
+    ExtractorStarted(SignatureResult),
+    /// An extractor finished running.
+    ExtractorFinished(SignatureResult, ExtractionResult),
+}
+
+/// Snapshot of scan/extraction progress, passed to a [`ProgressCallback`]
+/// once per signature match and once per extractor start/finish.
+#[derive(Debug, Clone)]
+pub struct ProgressInfo {
+    /// Offset the event occurred at.
+    pub offset: usize,
+    /// Number of bytes of the target file processed so far.
+    pub bytes_processed: usize,
+    /// Total size of the target file being processed.
+    pub total_size: usize,
+    /// What happened at this offset.
+    pub event: ProgressEvent,
+}
+
+/// Callback invoked during [`Binwalk::scan`]/[`Binwalk::extract`] to report
+/// progress.
+///
+/// Return `true` to continue, or `false` to abort the operation early; any
+/// results gathered up to that point are still returned to the caller.
+pub type ProgressCallback<'a> = dyn FnMut(&ProgressInfo) -> bool + 'a;
+
+/// Key a [`Binwalk::extract`]/[`Binwalk::extract_with_progress`] result is
+/// stored under for `signature`. Exposed so callers can recover scan order
+/// when iterating the returned `HashMap`, which does not preserve it.
+pub fn extraction_key(signature: &SignatureResult) -> String {
+    format!("{:X}.{}", signature.offset, signature.name)
+}
+
+/// Entry point for scanning and extracting data embedded in a target file.
+pub struct Binwalk {
+    /// Path to the file that was configured as the scan target.
+    pub base_target_file: String,
+    /// Directory extracted files are written under.
+    pub extraction_directory: String,
+    signatures: Vec<Box<dyn Signature>>,
+    extractors: HashMap<String, Box<dyn Extractor>>,
+    name_dictionary: Option<HashMap<u64, String>>,
+    full_search: bool,
+}
+
+impl Binwalk {
+    /// Creates a new `Binwalk` instance with default settings: all built-in
+    /// signatures enabled, no target file, and an empty extraction directory
+    /// (so [`Binwalk::extract`] writes relative to the current directory).
+    pub fn new() -> Self {
+        Binwalk {
+            base_target_file: String::new(),
+            extraction_directory: String::new(),
+            signatures: default_signatures(),
+            extractors: default_extractors(),
+            name_dictionary: None,
+            full_search: false,
+        }
+    }
+
+    /// Creates a configured `Binwalk` instance.
+    ///
+    /// ## Arguments
+    ///
+    /// * `target_file` - Path to the file to scan.
+    /// * `output_directory` - Directory to write extracted files to.
+    /// * `include_filters` - Only scan for signatures matching one of these
+    ///   patterns. Each pattern is `glob:<pattern>`, `re:<pattern>`, or an
+    ///   exact signature name (optionally prefixed with `name:`). See
+    ///   [`crate::signatures::PatternMatcher`].
+    /// * `exclude_filters` - Never scan for signatures matching one of these patterns.
+    /// * `custom_signatures` - Additional signature definitions to scan for.
+    /// * `full_search` - If `true`, keep searching for matches at every offset
+    ///   instead of skipping past bytes already claimed by a match.
+    /// * `name_dictionary_path` - Path to a newline-delimited list of
+    ///   candidate filenames. Extractors for formats that only record a name
+    ///   hash per entry (e.g. `hashfs`) use this to recover a human-readable
+    ///   name, falling back to the hash's hex string when no candidate matches.
+    pub fn configure(
+        target_file: Option<String>,
+        output_directory: Option<String>,
+        include_filters: Option<Vec<String>>,
+        exclude_filters: Option<Vec<String>>,
+        custom_signatures: Option<Vec<Box<dyn Signature>>>,
+        full_search: bool,
+        name_dictionary_path: Option<String>,
+    ) -> Result<Self, BinwalkError> {
+        let name_dictionary = name_dictionary_path
+            .map(|path| load_name_dictionary(&path))
+            .transpose()?;
+
+        let mut signatures = default_signatures();
+        signatures.extend(custom_signatures.unwrap_or_default());
+
+        let include_matcher: Box<dyn SignatureMatcher> = match include_filters {
+            Some(patterns) if !patterns.is_empty() => union_of(&patterns)?,
+            _ => Box::new(MatchAll),
+        };
+        let exclude_matcher: Box<dyn SignatureMatcher> = match exclude_filters {
+            Some(patterns) if !patterns.is_empty() => union_of(&patterns)?,
+            _ => Box::new(MatchNone),
+        };
+        let matcher = DifferenceMatcher {
+            include: include_matcher,
+            exclude: exclude_matcher,
+        };
+
+        signatures.retain(|sig| matcher.matches(sig.name()));
+
+        Ok(Binwalk {
+            base_target_file: target_file.unwrap_or_default(),
+            extraction_directory: output_directory.unwrap_or_else(|| "extractions".to_string()),
+            signatures,
+            extractors: default_extractors(),
+            name_dictionary,
+            full_search,
+        })
+    }
+
+    /// Scans `file_data` for signature matches.
+    pub fn scan(&self, file_data: &[u8]) -> AnalysisResults {
+        self.scan_with_progress(file_data, None)
+    }
+
+    /// Scans `file_data` for signature matches, invoking `progress` once per
+    /// match. Returns early with the partial results if `progress` returns
+    /// `false`.
+    pub fn scan_with_progress(
+        &self,
+        file_data: &[u8],
+        mut progress: Option<&mut ProgressCallback>,
+    ) -> AnalysisResults {
+        let total_size = file_data.len();
+        let mut results = AnalysisResults::new();
+        let mut offset = 0;
+
+        while offset < total_size {
+            let found = self
+                .signatures
+                .iter()
+                .find_map(|signature| signature.scan(file_data, offset));
+
+            match found {
+                Some(result) => {
+                    let next_offset = if self.full_search {
+                        offset + 1
+                    } else {
+                        offset + result.size.max(1)
+                    };
+
+                    if let Some(callback) = progress.as_deref_mut() {
+                        let info = ProgressInfo {
+                            offset: result.offset,
+                            bytes_processed: next_offset,
+                            total_size,
+                            event: ProgressEvent::SignatureMatch(result.clone()),
+                        };
+
+                        let keep_going = callback(&info);
+                        results.push(result);
+
+                        if !keep_going {
+                            break;
+                        }
+                    } else {
+                        results.push(result);
+                    }
+
+                    offset = next_offset;
+                }
+                None => offset += 1,
+            }
+        }
+
+        results
+    }
+
+    /// Extracts the data identified by `signatures` out of `file_data`.
+    pub fn extract(
+        &self,
+        file_data: &[u8],
+        target_file: &str,
+        signatures: &AnalysisResults,
+    ) -> HashMap<String, ExtractionResult> {
+        self.extract_with_progress(file_data, target_file, signatures, None)
+    }
+
+    /// Extracts the data identified by `signatures` out of `file_data`,
+    /// invoking `progress` once before and once after each extractor runs.
+    /// Returns early with the partial results if `progress` returns `false`.
+    pub fn extract_with_progress(
+        &self,
+        file_data: &[u8],
+        target_file: &str,
+        signatures: &AnalysisResults,
+        mut progress: Option<&mut ProgressCallback>,
+    ) -> HashMap<String, ExtractionResult> {
+        let _ = target_file;
+        let total_size = file_data.len();
+        let mut results = HashMap::new();
+        let carver = CarveExtractor;
+
+        for signature in signatures {
+            if let Some(callback) = progress.as_deref_mut() {
+                let info = ProgressInfo {
+                    offset: signature.offset,
+                    bytes_processed: signature.offset,
+                    total_size,
+                    event: ProgressEvent::ExtractorStarted(signature.clone()),
+                };
+                if !callback(&info) {
+                    break;
+                }
+            }
+
+            let extractor: &dyn Extractor = self
+                .extractors
+                .get(&signature.name)
+                .map(|boxed| boxed.as_ref())
+                .unwrap_or(&carver);
+
+            let result = extractor.extract(
+                file_data,
+                signature,
+                &self.extraction_directory,
+                self.name_dictionary.as_ref(),
+            );
+            let key = extraction_key(signature);
+
+            if let Some(callback) = progress.as_deref_mut() {
+                let info = ProgressInfo {
+                    offset: signature.offset,
+                    bytes_processed: signature.offset + signature.size,
+                    total_size,
+                    event: ProgressEvent::ExtractorFinished(signature.clone(), result.clone()),
+                };
+                let keep_going = callback(&info);
+                results.insert(key, result);
+                if !keep_going {
+                    break;
+                }
+            } else {
+                results.insert(key, result);
+            }
+        }
+
+        results
+    }
+}
+
+impl Default for Binwalk {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,39 @@
+//! Utility helpers shared across the crate.
+
+use std::collections::HashMap;
+use std::fs;
+
+use crate::binwalk::BinwalkError;
+
+/// Hashes `name`, using the same algorithm an extractor uses to look up
+/// candidate filenames in a name dictionary built by [`load_name_dictionary`].
+pub fn hash_name(name: &str) -> u64 {
+    const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const FNV_PRIME: u64 = 0x100000001b3;
+
+    let mut hash = FNV_OFFSET_BASIS;
+    for byte in name.as_bytes() {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+    hash
+}
+
+/// Reads `path`, a newline-delimited list of candidate filenames, and builds
+/// a lookup table from [`hash_name`] of each candidate to the candidate
+/// itself.
+///
+/// Blank lines are skipped. This lets an extractor recover a human-readable
+/// name for an archive entry that only records a name hash.
+pub fn load_name_dictionary(path: &str) -> Result<HashMap<u64, String>, BinwalkError> {
+    let contents = fs::read_to_string(path).map_err(|e| BinwalkError {
+        message: format!("failed to read name dictionary '{path}': {e}"),
+    })?;
+
+    Ok(contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|name| (hash_name(name), name.to_string()))
+        .collect())
+}
@@ -0,0 +1,97 @@
+//! A uniform way for extractors to shell out to external tools.
+
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::process::{Command as StdCommand, ExitStatus};
+
+use crate::binwalk::BinwalkError;
+
+/// Captured output of an external command that ran to completion.
+#[derive(Debug, Clone)]
+pub struct CommandOutput {
+    /// Bytes written to stdout.
+    pub stdout: Vec<u8>,
+    /// Bytes written to stderr.
+    pub stderr: Vec<u8>,
+    /// Exit status the command returned.
+    pub status: ExitStatus,
+}
+
+impl CommandOutput {
+    /// Returns `true` if the command exited with a zero status.
+    pub fn success(&self) -> bool {
+        self.status.success()
+    }
+}
+
+/// Runs `argv[0]` with the remaining elements of `argv` as arguments.
+///
+/// The child process runs in `working_directory` (typically the
+/// per-extraction output directory) with `env` merged into its environment.
+/// On success, returns the captured stdout, stderr and exit status. On
+/// failure, returns a [`BinwalkError`] whose message includes the full
+/// command line, the working directory, and captured stderr, so a failing
+/// extractor reports something actionable instead of a generic error.
+///
+/// Failing to locate `argv[0]` on `PATH` produces a distinct error message
+/// from the binary running and returning a non-zero exit status.
+pub fn command<S: AsRef<OsStr>>(
+    argv: &[S],
+    working_directory: &str,
+    env: Option<&HashMap<String, String>>,
+) -> Result<CommandOutput, BinwalkError> {
+    let Some((program, args)) = argv.split_first() else {
+        return Err(BinwalkError {
+            message: "command: no argv provided".to_string(),
+        });
+    };
+
+    let command_line = argv
+        .iter()
+        .map(|arg| arg.as_ref().to_string_lossy().into_owned())
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let mut child = StdCommand::new(program);
+    child.args(args);
+    child.current_dir(working_directory);
+
+    if let Some(env) = env {
+        for (key, value) in env {
+            child.env(key, value);
+        }
+    }
+
+    let output = child.output().map_err(|e| {
+        if e.kind() == std::io::ErrorKind::NotFound {
+            BinwalkError {
+                message: format!(
+                    "'{}' not found on PATH while running '{command_line}' (cwd: '{working_directory}')",
+                    program.as_ref().to_string_lossy()
+                ),
+            }
+        } else {
+            BinwalkError {
+                message: format!(
+                    "failed to spawn '{command_line}' (cwd: '{working_directory}'): {e}"
+                ),
+            }
+        }
+    })?;
+
+    if !output.status.success() {
+        return Err(BinwalkError {
+            message: format!(
+                "'{command_line}' (cwd: '{working_directory}') exited with {}: {}",
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ),
+        });
+    }
+
+    Ok(CommandOutput {
+        stdout: output.stdout,
+        stderr: output.stderr,
+        status: output.status,
+    })
+}
@@ -0,0 +1,193 @@
+//! Extraction of data carved out by a scan.
+
+pub mod command;
+
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::fs;
+use std::path::Path;
+
+use crate::signatures::SignatureResult;
+use command::command as run_command;
+
+/// Outcome of running an extractor against a single signature match.
+#[derive(Debug, Clone)]
+pub struct ExtractionResult {
+    /// Size, in bytes, of the data written to disk, if known.
+    pub size: Option<usize>,
+    /// Whether the extractor completed successfully.
+    pub success: bool,
+    /// Name of the extractor that produced this result.
+    pub extractor: String,
+    /// Directory the extracted data was written into.
+    pub output_directory: String,
+}
+
+/// Extracts the data belonging to a single signature match.
+///
+/// `Send + Sync` so a `Binwalk` instance can be shared across the GIL
+/// boundary while Python code runs concurrently with an extraction.
+pub trait Extractor: Send + Sync {
+    /// Name reported in [`ExtractionResult::extractor`].
+    fn name(&self) -> &str;
+
+    /// Carves the data for `result` out of `file_data` and writes it under
+    /// `output_directory`.
+    ///
+    /// `name_dictionary` is consulted by extractors for formats that only
+    /// store a name hash per entry, to recover a human-readable filename.
+    fn extract(
+        &self,
+        file_data: &[u8],
+        result: &SignatureResult,
+        output_directory: &str,
+        name_dictionary: Option<&HashMap<u64, String>>,
+    ) -> ExtractionResult;
+}
+
+/// Fallback extractor used for signatures with no dedicated extraction logic:
+/// carves the matched region out to a file named after the signature and offset.
+pub struct CarveExtractor;
+
+impl Extractor for CarveExtractor {
+    fn name(&self) -> &str {
+        "carve"
+    }
+
+    fn extract(
+        &self,
+        file_data: &[u8],
+        result: &SignatureResult,
+        output_directory: &str,
+        _name_dictionary: Option<&HashMap<u64, String>>,
+    ) -> ExtractionResult {
+        let end = (result.offset + result.size).min(file_data.len());
+        let carved = &file_data[result.offset..end];
+        let file_name = format!("{:X}.{}", result.offset, result.name);
+        let output_path = Path::new(output_directory).join(&file_name);
+
+        let success = fs::create_dir_all(output_directory)
+            .and_then(|_| fs::write(&output_path, carved))
+            .is_ok();
+
+        ExtractionResult {
+            size: success.then_some(carved.len()),
+            success,
+            extractor: self.name().to_string(),
+            output_directory: output_directory.to_string(),
+        }
+    }
+}
+
+/// Carves a SquashFS image out to `<offset>.squashfs` in the output directory
+/// and unpacks it with the external `unsquashfs` tool, via
+/// [`command::command`]. The carved image is left in place.
+pub struct SquashfsExtractor;
+
+impl Extractor for SquashfsExtractor {
+    fn name(&self) -> &str {
+        "unsquashfs"
+    }
+
+    fn extract(
+        &self,
+        file_data: &[u8],
+        result: &SignatureResult,
+        output_directory: &str,
+        _name_dictionary: Option<&HashMap<u64, String>>,
+    ) -> ExtractionResult {
+        let end = (result.offset + result.size).min(file_data.len());
+        let image_name = format!("{:X}.squashfs", result.offset);
+        let image_path = Path::new(output_directory).join(&image_name);
+
+        let carved = fs::create_dir_all(output_directory)
+            .and_then(|_| fs::write(&image_path, &file_data[result.offset..end]));
+
+        let argv: [&OsStr; 5] = [
+            OsStr::new("unsquashfs"),
+            OsStr::new("-f"),
+            OsStr::new("-d"),
+            OsStr::new("squashfs-root"),
+            // `unsquashfs` runs with `output_directory` as its working
+            // directory, so the image is named relative to it.
+            OsStr::new(&image_name),
+        ];
+
+        let success = carved.is_ok() && run_command(&argv, output_directory, None).is_ok();
+
+        ExtractionResult {
+            size: success.then_some(end - result.offset),
+            success,
+            extractor: self.name().to_string(),
+            output_directory: output_directory.to_string(),
+        }
+    }
+}
+
+/// Unpacks a `hashfs` archive: a sequence of `(hash: u64, size: u64, data)`
+/// entries following the signature's magic bytes.
+///
+/// Each entry's hash is looked up in `name_dictionary` to recover a
+/// human-readable filename; entries with no match are written under the
+/// hex string of their hash instead.
+pub struct HashedArchiveExtractor;
+
+impl Extractor for HashedArchiveExtractor {
+    fn name(&self) -> &str {
+        "hashfs"
+    }
+
+    fn extract(
+        &self,
+        file_data: &[u8],
+        result: &SignatureResult,
+        output_directory: &str,
+        name_dictionary: Option<&HashMap<u64, String>>,
+    ) -> ExtractionResult {
+        const MAGIC_LEN: usize = 4;
+        const ENTRY_HEADER_LEN: usize = 16;
+
+        let end = (result.offset + result.size).min(file_data.len());
+        let mut cursor = result.offset + MAGIC_LEN;
+        let mut bytes_written = 0;
+        let mut success = fs::create_dir_all(output_directory).is_ok();
+
+        while success && cursor + ENTRY_HEADER_LEN <= end {
+            let hash = u64::from_le_bytes(file_data[cursor..cursor + 8].try_into().unwrap());
+            let entry_size =
+                u64::from_le_bytes(file_data[cursor + 8..cursor + ENTRY_HEADER_LEN].try_into().unwrap())
+                    as usize;
+            cursor += ENTRY_HEADER_LEN;
+
+            if entry_size > end - cursor {
+                break;
+            }
+
+            let entry_name = name_dictionary
+                .and_then(|dictionary| dictionary.get(&hash))
+                .cloned()
+                .unwrap_or_else(|| format!("{hash:016x}"));
+            let entry_path = Path::new(output_directory).join(&entry_name);
+
+            success = fs::write(&entry_path, &file_data[cursor..cursor + entry_size]).is_ok();
+            bytes_written += entry_size;
+            cursor += entry_size;
+        }
+
+        ExtractionResult {
+            size: success.then_some(bytes_written),
+            success,
+            extractor: self.name().to_string(),
+            output_directory: output_directory.to_string(),
+        }
+    }
+}
+
+/// Builds the default extractor lookup table: one entry per signature name
+/// that has extraction support, falling back to [`CarveExtractor`] for the rest.
+pub fn default_extractors() -> HashMap<String, Box<dyn Extractor>> {
+    let mut extractors: HashMap<String, Box<dyn Extractor>> = HashMap::new();
+    extractors.insert("squashfs".to_string(), Box::new(SquashfsExtractor));
+    extractors.insert("hashfs".to_string(), Box::new(HashedArchiveExtractor));
+    extractors
+}
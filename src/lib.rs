@@ -27,8 +27,158 @@ pub use binwalk::{AnalysisResults, Binwalk, BinwalkError};
 // For Python bindings
 use pyo3::prelude::*;
 use pyo3::exceptions::PyRuntimeError;
-use std::collections::HashMap;
+use pyo3::types::PyDict;
 use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use binwalk::{extraction_key, ProgressCallback, ProgressEvent, ProgressInfo};
+use extractors::ExtractionResult;
+use signatures::SignatureResult;
+
+/// A signature match, returned to Python from [`scan_file`] and [`extract`].
+///
+/// Mirrors [`SignatureResult`], but with fields typed for Python rather than
+/// flattened into strings.
+#[pyclass(name = "SignatureResult")]
+#[derive(Debug, Clone)]
+struct PySignatureResult {
+    #[pyo3(get)]
+    offset: usize,
+    #[pyo3(get)]
+    size: usize,
+    #[pyo3(get)]
+    confidence: u8,
+    #[pyo3(get)]
+    name: String,
+    #[pyo3(get)]
+    description: String,
+    #[pyo3(get)]
+    id: String,
+}
+
+#[pymethods]
+impl PySignatureResult {
+    fn __repr__(&self) -> String {
+        format!(
+            "SignatureResult(offset={}, size={}, confidence={}, name='{}', id='{}')",
+            self.offset, self.size, self.confidence, self.name, self.id
+        )
+    }
+}
+
+impl From<SignatureResult> for PySignatureResult {
+    fn from(result: SignatureResult) -> Self {
+        PySignatureResult {
+            offset: result.offset,
+            size: result.size,
+            confidence: result.confidence,
+            name: result.name,
+            description: result.description,
+            id: result.id,
+        }
+    }
+}
+
+/// An extraction outcome, returned to Python from [`extract`].
+///
+/// Mirrors [`ExtractionResult`], but with `size` kept as an `Optional[int]`
+/// instead of the sentinel string `"Unknown"`.
+#[pyclass(name = "ExtractionResult")]
+#[derive(Debug, Clone)]
+struct PyExtractionResult {
+    #[pyo3(get)]
+    size: Option<usize>,
+    #[pyo3(get)]
+    success: bool,
+    #[pyo3(get)]
+    extractor: String,
+    #[pyo3(get)]
+    output_directory: String,
+}
+
+#[pymethods]
+impl PyExtractionResult {
+    fn __repr__(&self) -> String {
+        format!(
+            "ExtractionResult(size={:?}, success={}, extractor='{}', output_directory='{}')",
+            self.size, self.success, self.extractor, self.output_directory
+        )
+    }
+}
+
+impl From<ExtractionResult> for PyExtractionResult {
+    fn from(result: ExtractionResult) -> Self {
+        PyExtractionResult {
+            size: result.size,
+            success: result.success,
+            extractor: result.extractor,
+            output_directory: result.output_directory,
+        }
+    }
+}
+
+/// Converts a [`ProgressInfo`] into the `dict` handed to a Python progress
+/// callback.
+///
+/// Besides `offset`/`bytes_processed`/`total_size`/`stage`, the dict carries
+/// a `result` key holding the `SignatureResult` the event is about (as a
+/// `SignatureResult` pyclass), and, for `extractor_finished`, an additional
+/// `extraction_result` key holding the matching `ExtractionResult`.
+fn progress_info_to_pydict<'py>(py: Python<'py>, info: &ProgressInfo) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new(py);
+    dict.set_item("offset", info.offset)?;
+    dict.set_item("bytes_processed", info.bytes_processed)?;
+    dict.set_item("total_size", info.total_size)?;
+
+    let stage = match &info.event {
+        ProgressEvent::SignatureMatch(result) => {
+            dict.set_item("result", Py::new(py, PySignatureResult::from(result.clone()))?)?;
+            "signature_match"
+        }
+        ProgressEvent::ExtractorStarted(result) => {
+            dict.set_item("result", Py::new(py, PySignatureResult::from(result.clone()))?)?;
+            "extractor_started"
+        }
+        ProgressEvent::ExtractorFinished(result, extraction_result) => {
+            dict.set_item("result", Py::new(py, PySignatureResult::from(result.clone()))?)?;
+            dict.set_item(
+                "extraction_result",
+                Py::new(py, PyExtractionResult::from(extraction_result.clone()))?,
+            )?;
+            "extractor_finished"
+        }
+    };
+    dict.set_item("stage", stage)?;
+
+    Ok(dict)
+}
+
+/// Wraps an optional Python callable as a [`ProgressCallback`], acquiring the
+/// GIL only for the duration of each call so the surrounding scan/extract
+/// loop can run with the GIL released.
+///
+/// If the callback raises, the loop is stopped (as if it had returned
+/// `False`) and the exception is stashed in `error` instead of being
+/// swallowed, so the caller can propagate it once the loop exits.
+fn make_progress_callback(
+    callback: Option<PyObject>,
+    error: Arc<Mutex<Option<PyErr>>>,
+) -> impl FnMut(&ProgressInfo) -> bool {
+    move |info: &ProgressInfo| -> bool {
+        match &callback {
+            None => true,
+            Some(callback) => Python::with_gil(|py| -> PyResult<bool> {
+                let info_dict = progress_info_to_pydict(py, info)?;
+                let result = callback.call1(py, (info_dict,))?;
+                result.extract::<bool>(py)
+            })
+            .unwrap_or_else(|err| {
+                *error.lock().unwrap() = Some(err);
+                false
+            }),
+        }
+    }
+}
 
 
 /// Extracts data from a file using Binwalk.
@@ -37,32 +187,54 @@ use std::path::Path;
 ///
 /// * `file_path` - The path to the file to be analyzed.
 /// * `output_path` - The directory where extracted files will be saved.
-/// * `include` - Optional list of signatures to include in the analysis.
-/// * `exclude` - Optional list of signatures to exclude from the analysis.
+/// * `include` - Optional list of signature patterns to include in the
+///   analysis. Each pattern is `"glob:<pattern>"` (e.g. `"glob:lzma*"`),
+///   `"re:<pattern>"` (e.g. `"re:^(gzip|zlib)$"`), or an exact signature name.
+/// * `exclude` - Optional list of signature patterns to exclude from the
+///   analysis, using the same pattern syntax as `include`.
 /// * `full_search` - Optional flag to enable full search mode.
+/// * `callback` - Optional Python callable invoked once per signature match
+///   and once per extractor start/finish. It receives a single `dict`
+///   argument with `offset`, `bytes_processed`, `total_size`, `stage` and
+///   `result` (the matched `SignatureResult`) keys, plus `extraction_result`
+///   (the `ExtractionResult`) when `stage` is `"extractor_finished"`.
+///   Returning `False` aborts the scan/extraction early, with the results
+///   gathered so far still returned. Raising an exception also aborts early,
+///   but the exception propagates out of `extract` instead of being
+///   swallowed. The GIL is released while Rust code runs and only
+///   reacquired for the duration of each callback invocation.
+/// * `name_dictionary` - Optional path to a newline-delimited list of
+///   candidate filenames, used by extractors for formats that only record a
+///   name hash per entry (e.g. `hashfs`) to recover human-readable names.
 ///
 /// ## Returns
 ///
-/// A vector of hash maps containing the extraction results.
+/// A list of `ExtractionResult` objects, one per signature match, with
+/// `size` as `Optional[int]` (`None` when the extracted size is unknown)
+/// rather than the string `"Unknown"`.
 ///
 /// ## Example
 ///
 /// ```python
 /// from your_project_name import extract
 ///
-/// results = extract("path/to/file", "output/directory", None, None, False)
+/// results = extract("path/to/file", "output/directory", None, None, False,
+///                    callback=lambda info: print(info) or True)
 /// for result in results:
-///     print(result)
+///     print(result.size, result.success)
 /// ```
 #[pyfunction]
-#[pyo3(signature = (file_path, output_path=None, include=None, exclude=None, full_search=None))]
+#[pyo3(signature = (file_path, output_path=None, include=None, exclude=None, full_search=None, callback=None, name_dictionary=None))]
 fn extract(
+    py: Python<'_>,
     file_path: String,
     output_path: Option<String>,
     include: Option<Vec<String>>,
     exclude: Option<Vec<String>>,
     full_search: Option<bool>,
-) -> PyResult<Vec<HashMap<String, String>>> {
+    callback: Option<PyObject>,
+    name_dictionary: Option<String>,
+) -> PyResult<Vec<PyExtractionResult>> {
 
     // Check if input file exists
     if !Path::new(&file_path).exists() {
@@ -77,34 +249,49 @@ fn extract(
         exclude,
         None,
         full_search.unwrap_or(false),
+        name_dictionary,
     ).map_err(|e| PyRuntimeError::new_err(e.message.to_string()))?;
 
     // Read the file data so we can pass it to the scan function and extract results
     let file_data = std::fs::read(&binwalker.base_target_file)
         .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
 
-    let scan_results = binwalker.scan(&file_data);
-
-    // The previous scan results can now be passed to the extract function to extract the data
-    let extraction_results = binwalker.extract(
-        &file_data,
-        &binwalker.base_target_file,
-        &scan_results,
-    );
-
-    // Convert the extraction results to a format that can be returned to Python
-    let mut results = Vec::new();
-    for (key, value) in extraction_results.iter() {
-        let mut result_map = HashMap::new();
-        result_map.insert("key".to_string(), key.clone());
-        let size_str = value.size.map_or("Unknown".to_string(), |s| s.to_string());
-        result_map.insert("size".to_string(), size_str);
-        result_map.insert("success".to_string(), value.success.to_string());
-        result_map.insert("extractor".to_string(), value.extractor.clone());
-        result_map.insert("output_directory".to_string(), value.output_directory.clone());
-        results.push(result_map);
+    // Scanning and extraction run with the GIL released; `progress_callback`
+    // reacquires it only for the duration of each invocation of `callback`.
+    let callback_error = Arc::new(Mutex::new(None));
+    let mut progress_callback = make_progress_callback(callback, callback_error.clone());
+    let (scan_results, mut extraction_results) = py.allow_threads(|| {
+        let cb: &mut ProgressCallback = &mut progress_callback;
+        let scan_results = binwalker.scan_with_progress(&file_data, Some(cb));
+
+        // An exception raised by `callback` during the scan aborts extraction too
+        if callback_error.lock().unwrap().is_some() {
+            return (scan_results, Default::default());
+        }
+
+        // The previous scan results can now be passed to the extract function to extract the data
+        let extraction_results = binwalker.extract_with_progress(
+            &file_data,
+            &binwalker.base_target_file,
+            &scan_results,
+            Some(cb),
+        );
+
+        (scan_results, extraction_results)
+    });
+
+    if let Some(err) = callback_error.lock().unwrap().take() {
+        return Err(err);
     }
 
+    // `extraction_results` is a HashMap and does not preserve scan order, so
+    // walk `scan_results` (which does) and pull each entry out by its key.
+    let results = scan_results
+        .iter()
+        .filter_map(|signature| extraction_results.remove(&extraction_key(signature)))
+        .map(PyExtractionResult::from)
+        .collect();
+
     Ok(results)
 
 }
@@ -115,22 +302,32 @@ fn extract(
 /// ## Arguments
 ///
 /// * `file_path` - The path to the file to be scanned.
+/// * `callback` - Optional Python callable invoked once per signature match
+///   with a `dict` of `offset`, `bytes_processed`, `total_size`, `stage` and
+///   `result` (the matched `SignatureResult`). Returning `False` aborts the
+///   scan early. Raising an exception also aborts early, but the exception
+///   propagates out of `scan_file` instead of being swallowed. The GIL is
+///   released while Rust code runs and only reacquired for the duration of
+///   each callback invocation.
 ///
 /// ## Returns
 ///
-/// A vector of hash maps containing the scan results.
+/// A list of `SignatureResult` objects, one per signature match, with
+/// `offset`, `size` and `confidence` kept as real integers rather than
+/// strings.
 ///
 /// ## Example
 ///
 /// ```python
 /// from your_project_name import scan_file
 ///
-/// results = scan_file("path/to/file")
+/// results = scan_file("path/to/file", callback=lambda info: print(info) or True)
 /// for result in results:
-///     print(result)
+///     print(result.offset, result.name)
 /// ```
 #[pyfunction]
-fn scan_file(file_path: &str) -> PyResult<Vec<HashMap<String, String>>> {
+#[pyo3(signature = (file_path, callback=None))]
+fn scan_file(py: Python<'_>, file_path: &str, callback: Option<PyObject>) -> PyResult<Vec<PySignatureResult>> {
 
     // Check to see whether the input file exists before proceeding
     let file_data = std::fs::read(&Path::new(file_path)).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
@@ -138,25 +335,26 @@ fn scan_file(file_path: &str) -> PyResult<Vec<HashMap<String, String>>> {
     // Create a new Binwalk instance
     let binwalker = Binwalk::new();
 
-    // Define a vector to store the results of the scan
-    let mut results = Vec::new();
-
-    // Convert the extraction results to a format that can be returned to Python
-    for result in binwalker.scan(&file_data) {
-        let mut result_map = HashMap::new();
-        result_map.insert("description".to_string(), result.description.clone());
-        result_map.insert("id".to_string(), result.id.clone());
-        result_map.insert("name".to_string(), result.name.clone());
-        result_map.insert("confidence".to_string(), result.confidence.clone().to_string());
-        result_map.insert("offset".to_string(), result.offset.to_string());
-        result_map.insert("size".to_string(), result.size.to_string());
-        results.push(result_map);
+    // Scanning runs with the GIL released; `progress_callback` reacquires it
+    // only for the duration of each invocation of `callback`.
+    let callback_error = Arc::new(Mutex::new(None));
+    let mut progress_callback = make_progress_callback(callback, callback_error.clone());
+    let scan_results = py.allow_threads(|| {
+        binwalker.scan_with_progress(&file_data, Some(&mut progress_callback))
+    });
+
+    if let Some(err) = callback_error.lock().unwrap().take() {
+        return Err(err);
     }
-    Ok(results)
+
+    // Convert the scan results to the typed objects returned to Python
+    Ok(scan_results.into_iter().map(PySignatureResult::from).collect())
 }
 
 #[pymodule]
 fn binwalkpy(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PySignatureResult>()?;
+    m.add_class::<PyExtractionResult>()?;
     m.add_function(wrap_pyfunction!(scan_file, m)?)?;
     m.add_function(wrap_pyfunction!(extract, m)?)?;
     Ok(())
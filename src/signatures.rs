@@ -0,0 +1,230 @@
+//! Signature definitions and the results produced when they match.
+
+use regex::Regex;
+
+use crate::binwalk::BinwalkError;
+
+/// A single signature match found during a scan.
+#[derive(Debug, Clone)]
+pub struct SignatureResult {
+    /// Offset of the match within the scanned data.
+    pub offset: usize,
+    /// Size of the matched data, in bytes.
+    pub size: usize,
+    /// Confidence score for this match, from 0 (weak) to 255 (certain).
+    pub confidence: u8,
+    /// Short, machine-readable signature name, e.g. `"gzip"`.
+    pub name: String,
+    /// Human-readable description of the match.
+    pub description: String,
+    /// Unique identifier of the signature definition that produced this match.
+    pub id: String,
+}
+
+/// A registered signature definition: a name plus the logic used to detect it.
+///
+/// `Send + Sync` so a `Binwalk` instance can be shared across the GIL
+/// boundary while Python code runs concurrently with a scan.
+pub trait Signature: Send + Sync {
+    /// Machine-readable name for this signature, e.g. `"gzip"`.
+    fn name(&self) -> &str;
+
+    /// Human-readable description used when a match is reported.
+    fn description(&self) -> &str;
+
+    /// Scans `file_data` starting at `offset` and returns a match if the
+    /// signature is present there.
+    fn scan(&self, file_data: &[u8], offset: usize) -> Option<SignatureResult>;
+}
+
+macro_rules! magic_signature {
+    ($struct_name:ident, $name:expr, $description:expr, $magic:expr) => {
+        /// Built-in signature definition.
+        pub struct $struct_name;
+
+        impl Signature for $struct_name {
+            fn name(&self) -> &str {
+                $name
+            }
+
+            fn description(&self) -> &str {
+                $description
+            }
+
+            fn scan(&self, file_data: &[u8], offset: usize) -> Option<SignatureResult> {
+                let magic: &[u8] = $magic;
+                if file_data[offset..].starts_with(magic) {
+                    Some(SignatureResult {
+                        offset,
+                        size: file_data.len() - offset,
+                        confidence: 255,
+                        name: $name.to_string(),
+                        description: $description.to_string(),
+                        id: $name.to_string(),
+                    })
+                } else {
+                    None
+                }
+            }
+        }
+    };
+}
+
+magic_signature!(Gzip, "gzip", "gzip compressed data", &[0x1f, 0x8b]);
+magic_signature!(Zlib, "zlib", "zlib compressed data", &[0x78, 0x9c]);
+magic_signature!(Xz, "xz", "XZ compressed data", &[0xfd, 0x37, 0x7a, 0x58, 0x5a]);
+magic_signature!(LzmaAlone, "lzma", "raw LZMA compressed data", &[0x5d, 0x00, 0x00]);
+magic_signature!(Squashfs, "squashfs", "SquashFS filesystem", b"hsqs");
+magic_signature!(Jffs2, "jffs2", "JFFS2 filesystem", &[0x85, 0x19]);
+magic_signature!(Zip, "zip", "Zip archive data", &[0x50, 0x4b, 0x03, 0x04]);
+magic_signature!(
+    Hashfs,
+    "hashfs",
+    "archive with hashed entry names",
+    b"HFS1"
+);
+
+/// Returns the default set of built-in signatures used by a new [`crate::Binwalk`] instance.
+pub fn default_signatures() -> Vec<Box<dyn Signature>> {
+    vec![
+        Box::new(Gzip),
+        Box::new(Zlib),
+        Box::new(Xz),
+        Box::new(LzmaAlone),
+        Box::new(Squashfs),
+        Box::new(Jffs2),
+        Box::new(Zip),
+        Box::new(Hashfs),
+    ]
+}
+
+/// Matches signature names against a pattern, or a combination of patterns.
+///
+/// Implemented by [`PatternMatcher`] for a single pattern, and by
+/// [`UnionMatcher`], [`IntersectionMatcher`] and [`DifferenceMatcher`] for
+/// combining matchers together.
+pub trait SignatureMatcher {
+    /// Returns `true` if `name` satisfies this matcher.
+    fn matches(&self, name: &str) -> bool;
+}
+
+/// Matches a signature name against a single pattern.
+///
+/// A pattern string may carry a prefix selecting how it's interpreted:
+///
+/// * `glob:<pattern>` - a shell-style glob, e.g. `glob:lzma*`
+/// * `re:<pattern>` - a regular expression, e.g. `re:^(gzip|zlib)$`
+/// * `name:<pattern>` or no prefix - an exact signature name, e.g. `name:squashfs`
+pub enum PatternMatcher {
+    Glob(Regex),
+    Regex(Regex),
+    Name(String),
+}
+
+impl PatternMatcher {
+    /// Compiles `pattern` into a `PatternMatcher`.
+    ///
+    /// Returns a [`BinwalkError`] if a `glob:` or `re:` pattern fails to compile.
+    pub fn compile(pattern: &str) -> Result<Self, BinwalkError> {
+        if let Some(glob_pattern) = pattern.strip_prefix("glob:") {
+            let regex = Regex::new(&glob_to_regex(glob_pattern)).map_err(|e| BinwalkError {
+                message: format!("invalid glob pattern '{glob_pattern}': {e}"),
+            })?;
+            Ok(PatternMatcher::Glob(regex))
+        } else if let Some(re_pattern) = pattern.strip_prefix("re:") {
+            let regex = Regex::new(re_pattern).map_err(|e| BinwalkError {
+                message: format!("invalid regex pattern '{re_pattern}': {e}"),
+            })?;
+            Ok(PatternMatcher::Regex(regex))
+        } else {
+            let name = pattern.strip_prefix("name:").unwrap_or(pattern);
+            Ok(PatternMatcher::Name(name.to_string()))
+        }
+    }
+}
+
+impl SignatureMatcher for PatternMatcher {
+    fn matches(&self, name: &str) -> bool {
+        match self {
+            PatternMatcher::Glob(regex) | PatternMatcher::Regex(regex) => regex.is_match(name),
+            PatternMatcher::Name(exact) => exact == name,
+        }
+    }
+}
+
+/// Converts a shell-style glob (`*` and `?` wildcards) into an anchored regex.
+fn glob_to_regex(glob: &str) -> String {
+    let mut pattern = String::from("^");
+    for c in glob.chars() {
+        match c {
+            '*' => pattern.push_str(".*"),
+            '?' => pattern.push('.'),
+            '.' | '+' | '(' | ')' | '|' | '[' | ']' | '{' | '}' | '^' | '$' | '\\' => {
+                pattern.push('\\');
+                pattern.push(c);
+            }
+            _ => pattern.push(c),
+        }
+    }
+    pattern.push('$');
+    pattern
+}
+
+/// Matches if any of its child matchers match.
+pub struct UnionMatcher(pub Vec<Box<dyn SignatureMatcher>>);
+
+impl SignatureMatcher for UnionMatcher {
+    fn matches(&self, name: &str) -> bool {
+        self.0.iter().any(|matcher| matcher.matches(name))
+    }
+}
+
+/// Matches only if all of its child matchers match.
+pub struct IntersectionMatcher(pub Vec<Box<dyn SignatureMatcher>>);
+
+impl SignatureMatcher for IntersectionMatcher {
+    fn matches(&self, name: &str) -> bool {
+        self.0.iter().all(|matcher| matcher.matches(name))
+    }
+}
+
+/// Matches if `include` matches and `exclude` does not.
+pub struct DifferenceMatcher {
+    pub include: Box<dyn SignatureMatcher>,
+    pub exclude: Box<dyn SignatureMatcher>,
+}
+
+impl SignatureMatcher for DifferenceMatcher {
+    fn matches(&self, name: &str) -> bool {
+        self.include.matches(name) && !self.exclude.matches(name)
+    }
+}
+
+/// Matches every signature name; used as the `include` side of a
+/// [`DifferenceMatcher`] when no include patterns were given.
+pub struct MatchAll;
+
+impl SignatureMatcher for MatchAll {
+    fn matches(&self, _name: &str) -> bool {
+        true
+    }
+}
+
+/// Matches no signature name; used as the `exclude` side of a
+/// [`DifferenceMatcher`] when no exclude patterns were given.
+pub struct MatchNone;
+
+impl SignatureMatcher for MatchNone {
+    fn matches(&self, _name: &str) -> bool {
+        false
+    }
+}
+
+/// Compiles `patterns` and combines them into a single [`UnionMatcher`].
+pub fn union_of(patterns: &[String]) -> Result<Box<dyn SignatureMatcher>, BinwalkError> {
+    let matchers = patterns
+        .iter()
+        .map(|pattern| PatternMatcher::compile(pattern).map(|m| Box::new(m) as Box<dyn SignatureMatcher>))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(Box::new(UnionMatcher(matchers)))
+}